@@ -0,0 +1,67 @@
+//! Cycle-detection helper for simulations that run too many steps to
+//! execute directly but whose state eventually repeats (tetris-style
+//! falling-rock puzzles, repeated board transforms, and the like).
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Advance `state` via `step` until `target` steps have been simulated,
+/// short-circuiting through a detected cycle when one appears first.
+///
+/// `step` must be deterministic and `state` must fully capture everything
+/// that determines future states; anything not included in `S` cannot be
+/// accounted for once the cycle shortcut kicks in.
+pub fn run_with_cycle<S, F>(initial: S, mut step: F, target: u64) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, u64> = HashMap::new();
+    let mut history: Vec<S> = Vec::new();
+
+    let mut state = initial;
+    seen.insert(state.clone(), 0);
+    history.push(state.clone());
+
+    for i in 1..=target {
+        state = step(&state);
+
+        if let Some(&j) = seen.get(&state) {
+            let len = i - j;
+            let remaining = (target - j) % len;
+            return history[(j + remaining) as usize].clone();
+        }
+
+        seen.insert(state.clone(), i);
+        history.push(state.clone());
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_circuits_through_a_detected_cycle() {
+        // 0 -> 1 -> 2 -> 0 -> 1 -> 2 -> ... (cycle of length 3, no prefix)
+        let result = run_with_cycle(0u32, |s| (s + 1) % 3, 1_000_000_000);
+        assert_eq!(result, 1_000_000_000 % 3);
+    }
+
+    #[test]
+    fn handles_a_non_zero_prefix() {
+        // 0 -> 1 -> 2 -> 3 -> 3 -> 3 -> ... (prefix of length 3, then settles)
+        let seq = [0u32, 1, 2, 3];
+        let result =
+            run_with_cycle(0u32, |s| seq[(s + 1).min(3) as usize], 10);
+        assert_eq!(result, 3);
+    }
+
+    #[test]
+    fn returns_direct_state_when_target_is_smaller_than_the_cycle() {
+        let result = run_with_cycle(0u32, |s| s + 1, 5);
+        assert_eq!(result, 5);
+    }
+}
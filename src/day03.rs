@@ -12,71 +12,19 @@
 //! **Input Parsing**: Each line is a string of digits representing a bank.
 //!
 //! **Part 1 & 2 Strategy**: For each bank, select k digits in order to maximize
-//! the resulting number. Algorithm:
-//! - Find max digit among valid candidates
-//! - Try all positions with that digit recursively
-//! - Branching factor is small because we only try max-digit positions
+//! the resulting number. This is the standard "maximum subsequence of length
+//! k" problem: keeping k of n digits in order means dropping `n - k` of them.
+//! A monotonic stack does this in one left-to-right pass - for each digit,
+//! pop any smaller digits off the top of the stack while drops remain, then
+//! push the digit. Any drops left over after the pass come off the end.
+//! **Complexity**: O(n) per bank, since each digit is pushed and popped at
+//! most once.
 
 /// Parse input into a vector of strings (each line is a bank)
 fn parse_input(input: &str) -> Vec<String> {
     input.trim().lines().map(|s| s.trim().to_string()).collect()
 }
 
-/// Count unused characters from index i (exclusive) to end
-fn count_available(used: &[bool], start: usize) -> usize {
-    used[start..].iter().filter(|&&u| !u).count()
-}
-
-/// Recursively find the maximum k-digit number
-fn find_max(
-    chars: &[char],
-    start_idx: usize,
-    k: usize,
-    used: &mut [bool],
-) -> String {
-    if k == 0 {
-        return String::new();
-    }
-
-    let n = chars.len();
-
-    // Find maximum digit among valid candidates
-    let mut max_digit = '0';
-    for i in start_idx..n {
-        if used[i] {
-            continue;
-        }
-        let available = count_available(used, i + 1);
-        if available >= k - 1 && chars[i] > max_digit {
-            max_digit = chars[i];
-        }
-    }
-
-    // Try all positions with max_digit
-    let mut best = String::new();
-    for i in start_idx..n {
-        if used[i] || chars[i] != max_digit {
-            continue;
-        }
-
-        let available = count_available(used, i + 1);
-        if available < k - 1 {
-            continue;
-        }
-
-        used[i] = true;
-        let suffix = find_max(chars, i + 1, k - 1, used);
-        used[i] = false;
-
-        let current = format!("{}{}", chars[i], suffix);
-        if best.is_empty() || current > best {
-            best = current;
-        }
-    }
-
-    best
-}
-
 /// Find maximum k-digit number from s by selecting k digits in order.
 fn max_number(s: &str, k: usize) -> String {
     let chars: Vec<char> = s.chars().collect();
@@ -85,8 +33,19 @@ fn max_number(s: &str, k: usize) -> String {
         return String::new();
     }
 
-    let mut used = vec![false; n];
-    find_max(&chars, 0, k, &mut used)
+    let mut drop = n - k;
+    let mut stack: Vec<char> = Vec::with_capacity(n);
+
+    for &d in &chars {
+        while drop > 0 && stack.last().is_some_and(|&top| top < d) {
+            stack.pop();
+            drop -= 1;
+        }
+        stack.push(d);
+    }
+
+    stack.truncate(stack.len() - drop);
+    stack.into_iter().take(k).collect()
 }
 
 pub fn part_one(input: &str) -> u64 {
@@ -18,13 +18,15 @@
 //! **Part 1**: For each cell containing `@`, count adjacent `@` cells (8 directions).
 //! If count < 4, it's accessible. Sum all accessible rolls.
 //!
-//! **Part 2**: Iteratively find and remove all accessible rolls:
-//! - Create a mutable grid
-//! - Loop until no more rolls are removed:
-//!   - Find all accessible rolls in current state
-//!   - Mark them for removal
-//!   - Remove them and increment count
-//! - Return total removed
+//! **Part 2**: Remove accessible rolls with a worklist instead of
+//! rescanning the whole grid every pass:
+//! - Precompute each roll's live-neighbor count once
+//! - Seed a queue with every roll whose count is already < 4
+//! - Pop a roll, remove it, and decrement its live neighbors' counts,
+//!   queuing any neighbor that newly drops below 4
+//! - Total removed = number of rolls popped from the queue
+
+use std::collections::VecDeque;
 
 /// Parse input into a 2D grid
 fn parse_input(input: &str) -> Vec<Vec<char>> {
@@ -77,20 +79,71 @@ pub fn part_one(input: &str) -> usize {
     accessible.len()
 }
 
+/// Remove accessible rolls via a worklist: each roll's live-neighbor count
+/// is tracked incrementally (decremented as neighbors are removed) rather
+/// than recounted from scratch, so the total work is linear in the number
+/// of rolls plus removals instead of quadratic in the number of passes.
 pub fn part_two(input: &str) -> usize {
-    let mut grid = parse_input(input);
-    let mut total_removed = 0;
+    let grid = parse_input(input);
+    let rows = grid.len();
+    if rows == 0 {
+        return 0;
+    }
+    let cols = grid[0].len();
 
-    loop {
-        let accessible = find_accessible(&grid);
-        if accessible.is_empty() {
-            break;
+    let mut present = vec![vec![false; cols]; rows];
+    let mut live_neighbors = vec![vec![0u8; cols]; rows];
+    for row in 0..rows {
+        for col in 0..cols {
+            present[row][col] = grid[row][col] == '@';
         }
-        // Remove all accessible rolls
-        for (row, col) in &accessible {
-            grid[*row][*col] = '.';
+    }
+    for row in 0..rows {
+        for col in 0..cols {
+            if present[row][col] {
+                live_neighbors[row][col] = count_adjacent(&grid, row, col) as u8;
+            }
+        }
+    }
+
+    // `queued` guards against pushing the same roll twice while it's still
+    // waiting to be processed.
+    let mut queued = vec![vec![false; cols]; rows];
+    let mut queue: VecDeque<(usize, usize)> = VecDeque::new();
+    for row in 0..rows {
+        for col in 0..cols {
+            if present[row][col] && live_neighbors[row][col] < 4 {
+                queued[row][col] = true;
+                queue.push_back((row, col));
+            }
+        }
+    }
+
+    let mut total_removed = 0;
+    while let Some((row, col)) = queue.pop_front() {
+        present[row][col] = false;
+        total_removed += 1;
+
+        for dr in -1..=1 {
+            for dc in -1..=1 {
+                if dr == 0 && dc == 0 {
+                    continue;
+                }
+                let nr = row as i64 + dr;
+                let nc = col as i64 + dc;
+                if nr < 0 || nr >= rows as i64 || nc < 0 || nc >= cols as i64 {
+                    continue;
+                }
+                let (nr, nc) = (nr as usize, nc as usize);
+                if present[nr][nc] {
+                    live_neighbors[nr][nc] -= 1;
+                    if live_neighbors[nr][nc] < 4 && !queued[nr][nc] {
+                        queued[nr][nc] = true;
+                        queue.push_back((nr, nc));
+                    }
+                }
+            }
         }
-        total_removed += accessible.len();
     }
 
     total_removed
@@ -26,66 +26,15 @@
 //!
 //! **Complexity**: O(r * c) where r is rows and c is columns.
 
-/// Parse input into a grid of characters
-fn parse_grid(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
-        .collect()
-}
-
-/// A parsed number with its column position
-#[derive(Debug, Clone)]
-struct NumberAt {
-    value: u128,
-    col_start: usize,
-}
-
-/// Parse a row into a list of numbers with their positions
-fn parse_row(row: &[char]) -> Vec<NumberAt> {
-    let mut numbers = Vec::new();
-    let mut col = 0;
-
-    while col < row.len() {
-        while col < row.len() && !row[col].is_ascii_digit() {
-            col += 1;
-        }
-
-        if col >= row.len() {
-            break;
-        }
-
-        let start = col;
-        while col < row.len() && row[col].is_ascii_digit() {
-            col += 1;
-        }
-
-        let num_str: String = row[start..col].iter().collect();
-        if let Ok(value) = num_str.parse::<u128>() {
-            numbers.push(NumberAt {
-                value,
-                col_start: start,
-            });
-        }
-    }
-
-    numbers
-}
+use crate::grid::{scan_numbers, Grid};
 
 /// Find all separator columns (columns that are all spaces in all data rows)
-fn find_separators(grid: &[Vec<char>], num_data_rows: usize) -> Vec<usize> {
-    let max_cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+fn find_separators(grid: &Grid<char>, num_data_rows: usize) -> Vec<usize> {
     let mut separators = Vec::new();
 
-    for col in 0..max_cols {
-        let mut is_separator = true;
-        for row in grid.iter().take(num_data_rows) {
-            if col < row.len() && row[col] != ' ' {
-                is_separator = false;
-                break;
-            }
-        }
+    for col in 0..grid.width() {
+        let is_separator = (0..num_data_rows)
+            .all(|row| matches!(grid.get(row, col), None | Some(&' ')));
         if is_separator {
             separators.push(col);
         }
@@ -103,37 +52,29 @@ struct ProblemRange {
 }
 
 /// Find all problem ranges using separators as boundaries
-fn find_problem_ranges(
-    grid: &[Vec<char>],
-    num_data_rows: usize,
-) -> Vec<ProblemRange> {
+fn find_problem_ranges(grid: &Grid<char>, num_data_rows: usize) -> Vec<ProblemRange> {
     let separators = find_separators(grid, num_data_rows);
-    let op_row = &grid[grid.len() - 1];
-    let max_cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let op_row = grid.row(grid.height() - 1).unwrap_or(&[]);
+    let max_cols = grid.width();
 
     let mut problems = Vec::new();
+    let find_op = |start: usize, end: usize| {
+        op_row[start.min(op_row.len())..end.min(op_row.len())]
+            .iter()
+            .find(|&&ch| ch == '+' || ch == '*')
+            .copied()
+            .unwrap_or('+')
+    };
 
     // Start from column 0 (or after first separator if it's at 0)
     let mut start_col = 0;
 
     for &sep in &separators {
-        // Problem spans from start_col to sep (exclusive)
         if start_col < sep {
-            // Find operator in this range
-            let mut op = '+';
-            for col in start_col..sep {
-                if col < op_row.len() {
-                    let ch = op_row[col];
-                    if ch == '+' || ch == '*' {
-                        op = ch;
-                        break;
-                    }
-                }
-            }
             problems.push(ProblemRange {
                 col_start: start_col,
                 col_end: sep,
-                op,
+                op: find_op(start_col, sep),
             });
         }
         start_col = sep + 1;
@@ -141,145 +82,95 @@ fn find_problem_ranges(
 
     // Last problem from last separator to end
     if start_col < max_cols {
-        let mut op = '+';
-        for col in start_col..max_cols {
-            if col < op_row.len() {
-                let ch = op_row[col];
-                if ch == '+' || ch == '*' {
-                    op = ch;
-                    break;
-                }
-            }
-        }
         problems.push(ProblemRange {
             col_start: start_col,
             col_end: max_cols,
-            op,
+            op: find_op(start_col, max_cols),
         });
     }
 
     problems
 }
 
+/// Which problem (if any) contains column `col`.
+fn problem_at(problems: &[ProblemRange], col: usize) -> Option<usize> {
+    problems
+        .iter()
+        .position(|p| col >= p.col_start && col < p.col_end)
+}
+
+fn apply_op(op: char, numbers: &[u128]) -> u128 {
+    match op {
+        '+' => numbers.iter().sum(),
+        '*' => numbers.iter().product(),
+        _ => 0,
+    }
+}
+
 pub fn part_one(input: &str) -> u128 {
-    let grid = parse_grid(input);
-    if grid.is_empty() {
+    let grid = Grid::parse(input, |c| c);
+    if grid.height() == 0 {
         return 0;
     }
 
-    let num_data_rows = grid.len() - 1;
+    let num_data_rows = grid.height() - 1;
     let problems = find_problem_ranges(&grid, num_data_rows);
 
-    // Parse all data rows into numbers
-    let mut all_numbers: Vec<Vec<NumberAt>> = Vec::new();
-    for row in grid.iter().take(num_data_rows) {
-        all_numbers.push(parse_row(row));
-    }
-
     // Assign each number to the problem whose range contains it
-    let mut problem_numbers: Vec<Vec<u128>> =
-        vec![Vec::new(); problems.len()];
-
-    for row_numbers in &all_numbers {
-        for num in row_numbers {
-            // Find which problem range contains this number's start column
-            for (idx, problem) in problems.iter().enumerate() {
-                if num.col_start >= problem.col_start
-                    && num.col_start < problem.col_end
-                {
-                    problem_numbers[idx].push(num.value);
-                    break;
-                }
+    let mut problem_numbers: Vec<Vec<u128>> = vec![Vec::new(); problems.len()];
+    for row in 0..num_data_rows {
+        for (value, col_start) in scan_numbers(grid.row(row).unwrap_or(&[])) {
+            if let Some(idx) = problem_at(&problems, col_start) {
+                problem_numbers[idx].push(value);
             }
         }
     }
 
-    // Compute results
-    let mut total: u128 = 0;
-    for (idx, problem) in problems.iter().enumerate() {
-        let result: u128 = match problem.op {
-            '+' => problem_numbers[idx].iter().sum(),
-            '*' => problem_numbers[idx].iter().product(),
-            _ => 0,
-        };
-        total += result;
-    }
-
-    total
+    problems
+        .iter()
+        .zip(&problem_numbers)
+        .map(|(p, numbers)| apply_op(p.op, numbers))
+        .sum()
 }
 
 pub fn part_two(input: &str) -> u128 {
-    let grid = parse_grid(input);
-    if grid.is_empty() {
+    let grid = Grid::parse(input, |c| c);
+    if grid.height() == 0 {
         return 0;
     }
 
-    let num_data_rows = grid.len() - 1;
+    let num_data_rows = grid.height() - 1;
     let problems = find_problem_ranges(&grid, num_data_rows);
 
-    // Find max column width
-    let max_cols = grid.iter().map(|row| row.len()).max().unwrap_or(0);
-
     // For each character column, determine which problem it belongs to
-    // Then read that column top-to-bottom to form a number
-    let mut problem_numbers: Vec<Vec<(usize, u128)>> =
-        vec![Vec::new(); problems.len()];
-
-    for col in 0..max_cols {
-        // Check if this column has any digits
-        let mut has_digits = false;
-        for row in grid.iter().take(num_data_rows) {
-            if col < row.len() && row[col].is_ascii_digit() {
-                has_digits = true;
-                break;
-            }
-        }
+    // and read that column top-to-bottom to form a number.
+    let mut problem_numbers: Vec<Vec<(usize, u128)>> = vec![Vec::new(); problems.len()];
 
-        if !has_digits {
+    for col in 0..grid.width() {
+        let Some(idx) = problem_at(&problems, col) else {
             continue;
-        }
-
-        // Find which problem range contains this column
-        let mut problem_idx = None;
-        for (idx, problem) in problems.iter().enumerate() {
-            if col >= problem.col_start && col < problem.col_end {
-                problem_idx = Some(idx);
-                break;
-            }
-        }
+        };
 
-        if let Some(idx) = problem_idx {
-            // Read this column top-to-bottom to form a number
-            let mut num_str = String::new();
-            for row in grid.iter().take(num_data_rows) {
-                if col < row.len() && row[col].is_ascii_digit() {
-                    num_str.push(row[col]);
-                }
-            }
+        let digits: String = (0..num_data_rows)
+            .filter_map(|row| grid.get(row, col))
+            .filter(|ch| ch.is_ascii_digit())
+            .collect();
 
-            if let Ok(num) = num_str.parse::<u128>() {
-                problem_numbers[idx].push((col, num));
-            }
+        if let Ok(num) = digits.parse::<u128>() {
+            problem_numbers[idx].push((col, num));
         }
     }
 
-    // Compute results - numbers are processed right-to-left within each problem
-    let mut total: u128 = 0;
-    for (idx, problem) in problems.iter().enumerate() {
-        // Sort by column position in descending order (right-to-left)
-        problem_numbers[idx].sort_by_key(|&(col, _)| std::cmp::Reverse(col));
-        let numbers: Vec<u128> =
-            problem_numbers[idx].iter().map(|&(_, n)| n).collect();
-
-        let result: u128 = match problem.op {
-            '+' => numbers.iter().sum(),
-            '*' => numbers.iter().product(),
-            _ => 0,
-        };
-        total += result;
-    }
-
-    total
+    // Numbers are processed right-to-left within each problem.
+    problems
+        .iter()
+        .zip(&mut problem_numbers)
+        .map(|(p, numbers)| {
+            numbers.sort_by_key(|&(col, _)| std::cmp::Reverse(col));
+            let values: Vec<u128> = numbers.iter().map(|&(_, n)| n).collect();
+            apply_op(p.op, &values)
+        })
+        .sum()
 }
 
 #[cfg(test)]
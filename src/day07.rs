@@ -24,11 +24,19 @@
 //! - For each cell, propagate the count to cells below
 //! - Splitters split the count to left and right adjacent cells below
 //! - Count unique end positions (cells in bottom row with count > 0)
+//! - Accumulate with `BigUint` rather than a fixed-width integer, since a
+//!   grid with enough splitters along one path can produce a timeline
+//!   count past any fixed-width ceiling
 //!
 //! **Complexity**: O(h * w) where h is height and w is width of grid.
 
 use std::collections::{HashSet, VecDeque};
 
+use num_bigint::BigUint;
+use num_traits::{ToPrimitive, Zero};
+
+use crate::grid::Grid;
+
 /// Position in the grid
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Pos {
@@ -37,39 +45,19 @@ struct Pos {
 }
 
 /// Parse input into a grid and find the source position
-fn parse_input(input: &str) -> (Vec<Vec<char>>, Pos) {
-    let grid: Vec<Vec<char>> = input
-        .lines()
-        .filter(|line| !line.is_empty())
-        .map(|line| line.chars().collect())
-        .collect();
-
-    // Find source position
-    let mut source = Pos { row: 0, col: 0 };
-    for (row, line) in grid.iter().enumerate() {
-        for (col, &ch) in line.iter().enumerate() {
-            if ch == 'S' {
-                source = Pos { row, col };
-                break;
-            }
-        }
-        if source.row == row {
-            break;
-        }
-    }
-
-    (grid, source)
+fn parse_input(input: &str) -> (Grid<char>, Pos) {
+    let grid = Grid::parse(input, |c| c);
+    let (row, col) = grid.find(|&ch| ch == 'S').unwrap_or((0, 0));
+    (grid, Pos { row, col })
 }
 
 /// Count how many times the beam is split (unique splitters hit)
 pub fn part_one(input: &str) -> usize {
     let (grid, source) = parse_input(input);
-    if grid.is_empty() {
+    if grid.height() == 0 {
         return 0;
     }
 
-    let height = grid.len();
-
     // Track which splitters have been hit
     let mut hit_splitters: HashSet<Pos> = HashSet::new();
 
@@ -80,112 +68,96 @@ pub fn part_one(input: &str) -> usize {
     let mut queue: VecDeque<Pos> = VecDeque::new();
     queue.push_back(source);
 
-    while let Some(pos) = queue.pop_front() {
-        let Pos { row, col } = pos;
-
-        // Move down to next row
+    while let Some(Pos { row, col }) = queue.pop_front() {
         let next_row = row + 1;
-        if next_row >= height {
-            continue; // Exited grid
-        }
-
-        // Check what's in the cell below at the same column
-        if next_row < grid.len() {
-            let row_len = grid[next_row].len();
-            if col >= row_len {
-                continue; // Column out of bounds for this row
-            }
-
-            let ch = grid[next_row][col];
-            if ch == '^' {
-                // Hit a splitter
-                let splitter_pos = Pos { row: next_row, col };
-                if hit_splitters.insert(splitter_pos) {
-                    // Create two new beams going to adjacent columns in the SAME row
-                    // They will then continue down from there
-                    if col > 0 {
-                        let left_pos = Pos {
-                            row: next_row,
-                            col: col - 1,
-                        };
-                        if visited.insert(left_pos) {
-                            queue.push_back(left_pos);
-                        }
-                    }
-                    if col + 1 < row_len {
-                        let right_pos = Pos {
-                            row: next_row,
-                            col: col + 1,
-                        };
-                        if visited.insert(right_pos) {
-                            queue.push_back(right_pos);
-                        }
+        let Some(&ch) = grid.get(next_row, col) else {
+            continue; // exited the grid, or column out of bounds for this row
+        };
+
+        if ch == '^' {
+            // Hit a splitter - create two new beams in the same row, one
+            // column to either side, which continue down from there.
+            let splitter_pos = Pos { row: next_row, col };
+            if hit_splitters.insert(splitter_pos) {
+                if col > 0 {
+                    let left_pos = Pos { row: next_row, col: col - 1 };
+                    if visited.insert(left_pos) {
+                        queue.push_back(left_pos);
                     }
                 }
-            } else {
-                // Continue down from this column
-                let next_pos = Pos { row: next_row, col };
-                if visited.insert(next_pos) {
-                    queue.push_back(next_pos);
+                if grid.get(next_row, col + 1).is_some() {
+                    let right_pos = Pos { row: next_row, col: col + 1 };
+                    if visited.insert(right_pos) {
+                        queue.push_back(right_pos);
+                    }
                 }
             }
+        } else {
+            let next_pos = Pos { row: next_row, col };
+            if visited.insert(next_pos) {
+                queue.push_back(next_pos);
+            }
         }
     }
 
     hit_splitters.len()
 }
 
-/// Count unique timelines (end positions) for quantum particle
-pub fn part_two(input: &str) -> u128 {
-    let (grid, source) = parse_input(input);
-    if grid.is_empty() {
-        return 0;
+/// Count timelines (distinct paths, summed over every end position) for the
+/// quantum particle, optionally
+/// reducing running counts modulo `modulus` as they accumulate. Counts are
+/// `BigUint` rather than a fixed-width integer, so the exact path (no
+/// modulus) never silently wraps no matter how many splitters a grid has
+/// along one path.
+fn count_timelines(grid: &Grid<char>, source: Pos, modulus: Option<u128>) -> BigUint {
+    if grid.height() == 0 {
+        return BigUint::zero();
     }
 
-    let height = grid.len();
-    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let height = grid.height();
+    let width = grid.width();
+    let modulus = modulus.map(BigUint::from);
+    let reduce = |n: BigUint| match &modulus {
+        Some(m) => n % m,
+        None => n,
+    };
 
     // Count number of ways to reach each cell in the current row
     // ways[col] = number of timelines that reach this column at current row
-    let mut ways: Vec<u128> = vec![0; width];
-    ways[source.col] = 1;
+    let mut ways: Vec<BigUint> = vec![BigUint::zero(); width];
+    ways[source.col] = BigUint::from(1u32);
 
     // Process each row from top to bottom
     for row in 0..height {
-        let mut next_ways: Vec<u128> = vec![0; width];
+        let mut next_ways: Vec<BigUint> = vec![BigUint::zero(); width];
 
         for col in 0..width {
-            if ways[col] == 0 {
+            if ways[col].is_zero() {
                 continue;
             }
 
-            let current_ways = ways[col];
+            let current_ways = ways[col].clone();
             let next_row = row + 1;
 
-            if next_row >= height {
-                // Exit at bottom - add to next_ways to count this timeline
-                next_ways[col] += current_ways;
-                continue;
-            }
-
-            if col >= grid[next_row].len() {
-                // Column out of bounds, beam exits
-                next_ways[col] += current_ways;
-                continue;
-            }
-
-            let ch = grid[next_row][col];
-            if ch == '^' {
-                // Hit a splitter - timeline splits to both paths
-                if col > 0 {
-                    next_ways[col - 1] += current_ways;
+            match grid.get(next_row, col) {
+                None => {
+                    // Exited the grid (bottom, or column out of bounds for
+                    // the next row) - count this timeline.
+                    next_ways[col] = reduce(next_ways[col].clone() + current_ways);
                 }
-                if col + 1 < width {
-                    next_ways[col + 1] += current_ways;
+                Some(&'^') => {
+                    // Hit a splitter - timeline splits to both paths
+                    if col > 0 {
+                        next_ways[col - 1] =
+                            reduce(next_ways[col - 1].clone() + current_ways.clone());
+                    }
+                    if col + 1 < width {
+                        next_ways[col + 1] = reduce(next_ways[col + 1].clone() + current_ways);
+                    }
+                }
+                Some(_) => {
+                    next_ways[col] = reduce(next_ways[col].clone() + current_ways);
                 }
-            } else {
-                // No splitter - continue down
-                next_ways[col] += current_ways;
             }
         }
 
@@ -193,7 +165,25 @@ pub fn part_two(input: &str) -> u128 {
     }
 
     // Total timelines = sum of all ways at the bottom row
-    ways.iter().sum()
+    ways.into_iter().fold(BigUint::zero(), |total, w| reduce(total + w))
+}
+
+/// Exact count of unique timelines, as an arbitrary-precision `BigUint` -
+/// unlike a fixed-width integer, this never silently overflows no matter
+/// how many splitters a grid's paths pass through.
+pub fn part_two(input: &str) -> BigUint {
+    let (grid, source) = parse_input(input);
+    count_timelines(&grid, source, None)
+}
+
+/// Count of unique timelines modulo `modulus`, for callers that only need
+/// a bounded residue (e.g. to print a manageable number) rather than
+/// `part_two`'s full-precision result.
+pub fn part_two_mod(input: &str, modulus: u128) -> u128 {
+    let (grid, source) = parse_input(input);
+    count_timelines(&grid, source, Some(modulus))
+        .to_u128()
+        .expect("result reduced modulo a u128 always fits in a u128")
 }
 
 #[cfg(test)]
@@ -210,6 +200,18 @@ mod tests {
     #[test]
     fn test_part_two() {
         let input = read_example(7);
-        assert_eq!(part_two(&input), 40);
+        assert_eq!(part_two(&input), BigUint::from(40u32));
+    }
+
+    #[test]
+    fn test_part_two_mod_matches_exact_below_modulus() {
+        let input = read_example(7);
+        assert_eq!(part_two_mod(&input, 1_000_000_007), 40);
+    }
+
+    #[test]
+    fn test_part_two_mod_wraps_at_the_modulus() {
+        let input = read_example(7);
+        assert_eq!(part_two_mod(&input, 7), 40 % 7);
     }
 }
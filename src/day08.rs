@@ -14,8 +14,10 @@
 //!
 //! **Part 1 Strategy**:
 //! - Parse all 3D coordinates
-//! - Generate all pairwise distances (n*(n-1)/2 edges)
-//! - Sort edges by distance
+//! - Bucket points into a spatial hash grid keyed by coordinate/cell, and
+//!   only compute distances between points in the same or an adjacent
+//!   bucket, growing the bucket size until enough close pairs are provably
+//!   found (see `find_close_edges_until`)
 //! - Use Union-Find to track connected components (circuits)
 //! - Process closest pairs (skip if already connected, but still count them)
 //! - For the puzzle: make 1000 connection attempts (pairs to try connecting)
@@ -23,18 +25,16 @@
 //! - Count component sizes and multiply three largest
 //!
 //! **Part 2 Strategy**:
-//! - Continue processing remaining edges until all boxes in one circuit
+//! - Continue processing edges (widening the spatial search as needed)
+//!   until all boxes are in one circuit
 //! - Track the last edge that successfully connects two circuits
 //! - Return the product of X coordinates of that edge's endpoints
 //!
-//! **Complexity**:
-//! - Part 1: O(n^2 * log(n^2)) for sorting all pairwise distances
-//! - Part 2: O(n^2 * alpha(n)) for continued processing
-//! - Space: O(n^2) for storing all edges
-//!
-//! With n=1000, n^2 = 1,000,000 edges which is manageable.
+//! **Complexity**: close to O(k log k) for the k pairs actually needed,
+//! rather than generating and sorting all n*(n-1)/2 pairs up front - this
+//! scales far better than brute force once junction-box counts grow large.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// 3D point representing a junction box position
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -152,6 +152,94 @@ fn distance_squared(p1: Point, p2: Point) -> u64 {
     (dx * dx + dy * dy + dz * dz) as u64
 }
 
+/// Starting bucket size for the spatial grid: a rough guess at the spacing
+/// between neighbors, refined by doubling in `find_close_edges_until`.
+fn initial_cell(points: &[Point]) -> u64 {
+    let max_coord = points
+        .iter()
+        .flat_map(|p| [p.x, p.y, p.z])
+        .max()
+        .unwrap_or(1);
+    (max_coord / points.len().max(1) as u64).max(1)
+}
+
+/// Bucket points into a hash grid keyed by `(x/cell, y/cell, z/cell)` and
+/// only compute distances between points sharing a bucket or an adjacent
+/// one. Two points closer together than `cell` can differ by at most one
+/// bucket index per axis, so this is guaranteed to find every pair within
+/// `cell` of each other - it may also turn up some farther pairs, but those
+/// aren't guaranteed complete.
+fn close_edges(points: &[Point], cell: u64) -> Vec<Edge> {
+    let mut buckets: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        let key =
+            ((p.x / cell) as i64, (p.y / cell) as i64, (p.z / cell) as i64);
+        buckets.entry(key).or_default().push(i);
+    }
+
+    let mut edges = Vec::new();
+    let mut seen: HashSet<(usize, usize)> = HashSet::new();
+
+    for (&(bx, by, bz), indices) in &buckets {
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(neighbors) =
+                        buckets.get(&(bx + dx, by + dy, bz + dz))
+                    else {
+                        continue;
+                    };
+                    for &i in indices {
+                        for &j in neighbors {
+                            if i < j && seen.insert((i, j)) {
+                                edges.push(Edge {
+                                    from: i,
+                                    to: j,
+                                    dist_sq: distance_squared(
+                                        points[i], points[j],
+                                    ),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+/// Find the `needed` globally closest edges (or all of them, if fewer
+/// exist), growing the grid's bucket size until enough are provably
+/// complete: once the `needed`-th smallest edge found is no farther than
+/// the current bucket size, nothing closer could be missing.
+fn find_close_edges_until(points: &[Point], needed: usize) -> Vec<Edge> {
+    let n = points.len();
+    let total_pairs = n * n.saturating_sub(1) / 2;
+    let needed = needed.min(total_pairs);
+    if needed == 0 {
+        return Vec::new();
+    }
+
+    let mut cell = initial_cell(points);
+    loop {
+        let mut edges = close_edges(points, cell);
+        edges.sort_by_key(|e| e.dist_sq);
+
+        let complete = edges.len() >= total_pairs
+            || (edges.len() >= needed
+                && edges[needed - 1].dist_sq <= cell * cell);
+
+        if complete {
+            // Past index `needed - 1` the list may be missing closer pairs.
+            edges.truncate(needed);
+            return edges;
+        }
+        cell *= 2;
+    }
+}
+
 /// Process connections to find circuit information
 fn process_circuits(
     points: &[Point],
@@ -159,52 +247,51 @@ fn process_circuits(
     find_last_edge: bool,
 ) -> (Vec<usize>, Option<(u64, u64)>) {
     let n = points.len();
+    let total_pairs = n * n.saturating_sub(1) / 2;
 
-    // Generate all pairwise edges
-    let mut edges: Vec<Edge> = Vec::with_capacity(n * n / 2);
-    for i in 0..n {
-        for j in (i + 1)..n {
-            let dist_sq = distance_squared(points[i], points[j]);
-            edges.push(Edge {
-                from: i,
-                to: j,
-                dist_sq,
-            });
-        }
-    }
+    // `target_attempts` of usize::MAX means "process until everything is
+    // one circuit", so start from a small guess and grow it instead of
+    // asking for every pair up front.
+    let mut needed = if target_attempts == usize::MAX {
+        n.max(2)
+    } else {
+        target_attempts.min(total_pairs).max(1)
+    };
 
-    // Sort by distance (ascending)
-    edges.sort_by_key(|e| e.dist_sq);
+    loop {
+        let edges = find_close_edges_until(points, needed);
 
-    let mut uf = UnionFind::new(n);
-    let mut attempts = 0;
-    let mut last_edge_x_product: Option<(u64, u64)> = None;
+        let mut uf = UnionFind::new(n);
+        let mut attempts = 0;
+        let mut last_edge_x_product: Option<(u64, u64)> = None;
+        let mut spanned = false;
 
-    for edge in &edges {
-        attempts += 1;
+        for edge in &edges {
+            attempts += 1;
 
-        // Try to connect - even if already connected, we count the attempt
-        if uf.union(edge.from, edge.to) {
-            // Check if this is the connection that makes everything one circuit
-            let components = uf.component_count();
-            if components == 1 {
+            // Try to connect - even if already connected, we count the attempt
+            if uf.union(edge.from, edge.to) && uf.component_count() == 1 {
                 if find_last_edge {
                     last_edge_x_product =
                         Some((points[edge.from].x, points[edge.to].x));
                 }
+                spanned = true;
                 break;
             }
+
+            // Count attempts (pairs we try to connect), not successful connections
+            if attempts >= target_attempts {
+                return (uf.component_sizes(), last_edge_x_product);
+            }
         }
 
-        // Count attempts (pairs we try to connect), not successful connections
-        if attempts >= target_attempts {
-            let sizes = uf.component_sizes();
-            return (sizes, last_edge_x_product);
+        if spanned || edges.len() >= total_pairs {
+            return (uf.component_sizes(), last_edge_x_product);
         }
-    }
 
-    let sizes = uf.component_sizes();
-    (sizes, last_edge_x_product)
+        // Not enough edges yet to finish spanning; widen the search.
+        needed *= 2;
+    }
 }
 
 /// Part 1: Multiply sizes of three largest circuits after making connection attempts
@@ -16,13 +16,14 @@
 //!
 //! **Part 2 Strategy**: Check if rectangle contains only red/green tiles:
 //! - Green tiles = perimeter (connecting red tiles) + interior
-//! - Use ray casting for point-in-polygon test
-//! - For each rectangle, sample points to check validity
-//! - Sampling strategy: check all points for small rectangles,
-//!   strategic sampling for large ones
+//! - Rasterize the closed (rectilinear) polygon into a dense grid over its
+//!   bounding box with a scanline fill, also marking every perimeter and red
+//!   tile as filled
+//! - Build a 2D prefix sum over that grid so a rectangle's filled-cell count
+//!   is an O(1) lookup, and a rectangle is valid iff that count equals its area
 //!
-//! **Complexity**: O(n²) for part 1, O(n² * k) for part 2 where n is
-//! the number of red tiles and k is the sampling cost per rectangle.
+//! **Complexity**: O(n²) for part 1. Part 2 preprocessing is O(W·H) for the
+//! bounding box, then each of the O(n²) pairs is an O(1) prefix-sum test.
 
 use std::collections::HashSet;
 
@@ -67,53 +68,97 @@ fn get_perimeter_points(red_tiles: &[(i64, i64)]) -> HashSet<(i64, i64)> {
     perimeter
 }
 
-/// Check if point q is on segment pr
-fn on_segment(q: (i64, i64), p: (i64, i64), r: (i64, i64)) -> bool {
-    let (qx, qy) = q;
-    let (px, py) = p;
-    let (rx, ry) = r;
-
-    // Check if q is collinear with p and r
-    let cross = (qx - px) * (ry - py) - (qy - py) * (rx - px);
-    if cross != 0 {
-        return false;
-    }
-
-    let min_x = px.min(rx);
-    let max_x = px.max(rx);
-    let min_y = py.min(ry);
-    let max_y = py.max(ry);
-
-    qx >= min_x && qx <= max_x && qy >= min_y && qy <= max_y
+/// A dense rasterization of the polygon's bounding box, with a 2D prefix
+/// sum over filled cells so any axis-aligned rectangle's filled-cell count
+/// is an O(1) lookup.
+struct FilledGrid {
+    min_x: i64,
+    min_y: i64,
+    width: usize,
+    /// `pref[y][x]` is the count of filled cells in `[0..x) x [0..y)`.
+    pref: Vec<Vec<i64>>,
 }
 
-/// Check if a point is inside the polygon using ray casting
-fn is_inside_polygon(point: (i64, i64), polygon: &[(i64, i64)]) -> bool {
-    let (x, y) = point;
-    let mut inside = false;
-    let n = polygon.len();
-
-    for i in 0..n {
-        let (x1, y1) = polygon[i];
-        let (x2, y2) = polygon[(i + 1) % n];
+impl FilledGrid {
+    /// Rasterize the rectilinear polygon formed by `red_tiles` (in order)
+    /// into the grid covering its bounding box, marking filled cells via a
+    /// scanline fill plus the perimeter and red tiles themselves.
+    fn build(
+        red_tiles: &[(i64, i64)],
+        perimeter: &HashSet<(i64, i64)>,
+    ) -> Self {
+        let min_x = red_tiles.iter().map(|&(x, _)| x).min().unwrap();
+        let max_x = red_tiles.iter().map(|&(x, _)| x).max().unwrap();
+        let min_y = red_tiles.iter().map(|&(_, y)| y).min().unwrap();
+        let max_y = red_tiles.iter().map(|&(_, y)| y).max().unwrap();
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut filled = vec![vec![false; width]; height];
+
+        let n = red_tiles.len();
+        for y in min_y..=max_y {
+            // Vertical edges crossing this scanline, by x.
+            let mut xs: Vec<i64> = Vec::new();
+            for i in 0..n {
+                let (x1, y1) = red_tiles[i];
+                let (x2, y2) = red_tiles[(i + 1) % n];
+                if x1 != x2 {
+                    continue; // horizontal edge, doesn't cross a scanline
+                }
+                let (lo, hi) = (y1.min(y2), y1.max(y2));
+                if lo <= y && y < hi {
+                    xs.push(x1);
+                }
+            }
+            xs.sort_unstable();
+
+            for pair in xs.chunks(2) {
+                if let [x0, x1] = pair {
+                    for x in *x0..=*x1 {
+                        filled[(y - min_y) as usize][(x - min_x) as usize] =
+                            true;
+                    }
+                }
+            }
+        }
 
-        // Check if point is on an edge
-        if on_segment(point, (x1, y1), (x2, y2)) {
-            return true;
+        for &(x, y) in red_tiles {
+            filled[(y - min_y) as usize][(x - min_x) as usize] = true;
+        }
+        for &(x, y) in perimeter {
+            filled[(y - min_y) as usize][(x - min_x) as usize] = true;
         }
 
-        // Ray casting algorithm
-        if (y1 > y) != (y2 > y) {
-            let x_intersect = x2 as f64
-                - (y2 as f64 - y as f64) * (x2 as f64 - x1 as f64)
-                    / (y2 as f64 - y1 as f64);
-            if (x as f64) < x_intersect {
-                inside = !inside;
+        let mut pref = vec![vec![0i64; width + 1]; height + 1];
+        for y in 0..height {
+            for x in 0..width {
+                pref[y + 1][x + 1] = pref[y][x + 1] + pref[y + 1][x]
+                    - pref[y][x]
+                    + filled[y][x] as i64;
             }
         }
+
+        FilledGrid {
+            min_x,
+            min_y,
+            width,
+            pref,
+        }
     }
 
-    inside
+    /// Count filled cells in the inclusive rectangle `[x1..=x2] x [y1..=y2]`.
+    fn count(&self, x1: i64, y1: i64, x2: i64, y2: i64) -> i64 {
+        let (c0, c1) =
+            ((x1 - self.min_x) as usize, (x2 - self.min_x) as usize);
+        let (r0, r1) =
+            ((y1 - self.min_y) as usize, (y2 - self.min_y) as usize);
+        debug_assert!(c1 < self.width);
+
+        self.pref[r1 + 1][c1 + 1] - self.pref[r0][c1 + 1]
+            - self.pref[r1 + 1][c0]
+            + self.pref[r0][c0]
+    }
 }
 
 pub fn part_one(input: &str) -> i64 {
@@ -139,85 +184,26 @@ pub fn part_one(input: &str) -> i64 {
     max_area
 }
 
-/// Check if a rectangle contains only red or green tiles
+/// Check if a rectangle contains only red or green tiles, via the O(1)
+/// prefix-sum count against its area.
 fn is_valid_rectangle(
     x1: i64,
     y1: i64,
     x2: i64,
     y2: i64,
-    red_tiles: &HashSet<(i64, i64)>,
-    perimeter: &HashSet<(i64, i64)>,
-    polygon: &[(i64, i64)],
+    grid: &FilledGrid,
 ) -> bool {
     let (min_x, max_x) = if x1 <= x2 { (x1, x2) } else { (x2, x1) };
     let (min_y, max_y) = if y1 <= y2 { (y1, y2) } else { (y2, y1) };
 
-    // Check all points for rectangles up to 10,000 area
     let area = (max_x - min_x + 1) * (max_y - min_y + 1);
-    if area <= 10000 {
-        for x in min_x..=max_x {
-            for y in min_y..=max_y {
-                let tile = (x, y);
-                if !red_tiles.contains(&tile)
-                    && !perimeter.contains(&tile)
-                    && !is_inside_polygon(tile, polygon)
-                {
-                    return false;
-                }
-            }
-        }
-        return true;
-    }
-
-    // For larger rectangles, use dense sampling
-    // We sample enough points to detect any significant region of invalid tiles
-    let step = ((area as f64).sqrt() / 20.0).ceil() as usize;
-
-    // Sample interior
-    for x in (min_x..=max_x).step_by(step.max(1)) {
-        for y in (min_y..=max_y).step_by(step.max(1)) {
-            let tile = (x, y);
-            if !red_tiles.contains(&tile)
-                && !perimeter.contains(&tile)
-                && !is_inside_polygon(tile, polygon)
-            {
-                return false;
-            }
-        }
-    }
-
-    // Check boundary points more carefully (every 100 points)
-    let boundary_step = 100;
-    for x in (min_x..=max_x).step_by(boundary_step) {
-        for y in [min_y, max_y].iter() {
-            let tile = (x, *y);
-            if !red_tiles.contains(&tile)
-                && !perimeter.contains(&tile)
-                && !is_inside_polygon(tile, polygon)
-            {
-                return false;
-            }
-        }
-    }
-    for y in ((min_y + boundary_step as i64)..max_y).step_by(boundary_step) {
-        for x in [min_x, max_x].iter() {
-            let tile = (*x, y);
-            if !red_tiles.contains(&tile)
-                && !perimeter.contains(&tile)
-                && !is_inside_polygon(tile, polygon)
-            {
-                return false;
-            }
-        }
-    }
-
-    true
+    grid.count(min_x, min_y, max_x, max_y) == area
 }
 
 pub fn part_two(input: &str) -> i64 {
     let red_tiles = parse_input(input);
-    let red_set: HashSet<(i64, i64)> = red_tiles.iter().cloned().collect();
     let perimeter = get_perimeter_points(&red_tiles);
+    let grid = FilledGrid::build(&red_tiles, &perimeter);
     let mut max_area = 0i64;
 
     // Brute force all pairs of red tiles
@@ -230,11 +216,7 @@ pub fn part_two(input: &str) -> i64 {
             let height = (y1 - y2).abs() + 1;
             let area = width * height;
 
-            if area > max_area
-                && is_valid_rectangle(
-                    x1, y1, x2, y2, &red_set, &perimeter, &red_tiles,
-                )
-            {
+            if area > max_area && is_valid_rectangle(x1, y1, x2, y2, &grid) {
                 max_area = area;
             }
         }
@@ -7,11 +7,22 @@
 //!
 //! ## Solution Approach
 //!
-//! **Part 1 Strategy**: BFS for XOR operations in GF(2).
-//! **Part 2 Strategy**: Gaussian elimination over rationals to reduce system,
-//! then search over free variables for optimal integer solution.
+//! **Part 1 Strategy**: row-reduce the button/light system over GF(2), then
+//! walk its null space in Gray-code order for the minimum-weight solution.
+//! **Part 2 Strategy**: Gaussian elimination over exact rationals to reduce
+//! the system, then a least-cost branch-and-bound over the free variables
+//! for the optimal integer solution. The search is anytime: given an
+//! optional wall-clock budget, it returns the best solution found so far
+//! instead of running to exhaustion.
 
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+
+use crate::dlx::Dlx;
 
 #[derive(Debug, Clone)]
 struct Machine {
@@ -67,147 +78,161 @@ fn parse_input(input: &str) -> Vec<Machine> {
 
 // ============== PART 1 ==============
 
+/// Minimum button presses to reach `target_lights`, found by solving the
+/// button/light toggle system exactly over GF(2) rather than exploring the
+/// (potentially exponential) state space by BFS.
+///
+/// Each light `i` gives one equation `sum(x_j for button j touching light i)
+/// = target[i] (mod 2)`; each button `j` out-of-range index is dropped while
+/// building the matrix, matching the toggle behavior used elsewhere. Row
+/// columns pack one bit per button plus an augmented bit for the target, so
+/// row operations are single `u128` XORs.
 fn solve_lights(machine: &Machine) -> usize {
     let target = &machine.target_lights;
 
-    if target.is_empty() {
+    if target.is_empty() || target.iter().all(|&x| !x) {
         return 0;
     }
 
     let num_buttons = machine.buttons.len();
+    debug_assert!(
+        num_buttons < 128,
+        "GF(2) rows pack one bit per button (plus the target) into a u128"
+    );
 
-    if target.iter().all(|&x| !x) {
-        return 0;
-    }
-
-    if num_buttons <= 20 {
-        bfs_solve_lights(machine)
-    } else {
-        iterative_solve_lights(machine)
+    let rhs_bit = 1u128 << num_buttons;
+    let mut rows: Vec<u128> = vec![0; target.len()];
+    for (j, button) in machine.buttons.iter().enumerate() {
+        for &light in button {
+            if light < rows.len() {
+                rows[light] |= 1 << j;
+            }
+        }
     }
-}
-
-fn bfs_solve_lights(machine: &Machine) -> usize {
-    let start = vec![false; machine.target_lights.len()];
-    let target = &machine.target_lights;
-    let num_buttons = machine.buttons.len();
-
-    let mut queue: VecDeque<(Vec<bool>, Vec<bool>)> = VecDeque::new();
-    let mut visited: std::collections::HashSet<Vec<bool>> =
-        std::collections::HashSet::new();
-
-    queue.push_back((vec![false; num_buttons], start.clone()));
-    visited.insert(start.clone());
-
-    while let Some((pressed, state)) = queue.pop_front() {
-        if state == *target {
-            return pressed.iter().filter(|&&x| x).count();
+    for (row, &lit) in rows.iter_mut().zip(target.iter()) {
+        if lit {
+            *row |= rhs_bit;
         }
+    }
 
-        for i in 0..num_buttons {
-            if pressed[i] {
-                continue;
-            }
-
-            let mut new_state = state.clone();
-            for &light_idx in &machine.buttons[i] {
-                if light_idx < new_state.len() {
-                    new_state[light_idx] = !new_state[light_idx];
-                }
-            }
-
-            if visited.insert(new_state.clone()) {
-                let mut new_pressed = pressed.clone();
-                new_pressed[i] = true;
-                queue.push_back((new_pressed, new_state));
+    // Row-reduce to echelon form, recording which column each pivot row
+    // fixed. Every other row with that column's bit set is also XORed so
+    // the result is already in reduced (not just row-echelon) form.
+    let mut pivot_cols = Vec::new();
+    let mut pivot_row = 0;
+    for col in 0..num_buttons {
+        let col_bit = 1u128 << col;
+        let Some(found) = (pivot_row..rows.len()).find(|&r| rows[r] & col_bit != 0) else {
+            continue;
+        };
+        rows.swap(pivot_row, found);
+        for other in 0..rows.len() {
+            if other != pivot_row && rows[other] & col_bit != 0 {
+                rows[other] ^= rows[pivot_row];
             }
         }
+        pivot_cols.push(col);
+        pivot_row += 1;
     }
 
-    iterative_solve_lights(machine)
-}
+    assert!(
+        rows[pivot_row..].iter().all(|&r| r & rhs_bit == 0),
+        "factory button sets are always able to reach their target lights"
+    );
 
-fn iterative_solve_lights(machine: &Machine) -> usize {
-    let mut state = vec![false; machine.target_lights.len()];
-    let mut presses = 0;
-    let target = &machine.target_lights;
+    let free_cols: Vec<usize> = (0..num_buttons).filter(|c| !pivot_cols.contains(c)).collect();
 
-    for _ in 0..1000 {
-        if state == *target {
-            return presses;
+    // Particular solution: every free variable set to 0, pivot variables
+    // back-substituted from the (already-reduced) augmented column.
+    let mut particular = 0u128;
+    for (r, &col) in pivot_cols.iter().enumerate() {
+        if rows[r] & rhs_bit != 0 {
+            particular |= 1 << col;
         }
+    }
 
-        let mut best_button: Option<usize> = None;
-        let mut best_score = 0i32;
-
-        for (i, button) in machine.buttons.iter().enumerate() {
-            let mut score = 0i32;
-
-            for &light_idx in button {
-                if light_idx >= state.len() {
-                    continue;
-                }
-
-                let current = state[light_idx];
-                let goal = target[light_idx];
-
-                if !current && goal {
-                    score += 1;
-                } else if current && !goal {
-                    score -= 1;
+    // One null-space basis vector per free column: flipping that free
+    // variable alone, with pivot variables adjusted to keep every equation
+    // satisfied (homogeneous, since the target cancels out of the null
+    // space).
+    let basis: Vec<u128> = free_cols
+        .iter()
+        .map(|&free_col| {
+            let free_bit = 1u128 << free_col;
+            let mut vector = free_bit;
+            for (r, &col) in pivot_cols.iter().enumerate() {
+                if rows[r] & free_bit != 0 {
+                    vector |= 1 << col;
                 }
             }
+            vector
+        })
+        .collect();
 
-            if score > best_score {
-                best_score = score;
-                best_button = Some(i);
-            }
-        }
-
-        if best_score <= 0 {
-            let mut found = false;
+    // The full solution set is `particular XOR` any subset of `basis`.
+    // Gray-code order flips exactly one basis vector per step, so the
+    // running solution (and its popcount) update incrementally instead of
+    // being recomputed from scratch for each of the 2^free_cols.len()
+    // subsets.
+    let mut current = particular;
+    let mut best = current.count_ones();
+    for k in 1u128..(1u128 << basis.len()) {
+        let flipped = (k ^ (k >> 1)) ^ ((k - 1) ^ ((k - 1) >> 1));
+        current ^= basis[flipped.trailing_zeros() as usize];
+        best = best.min(current.count_ones());
+    }
 
-            for (i, button) in machine.buttons.iter().enumerate() {
-                let mut affects_wrong = false;
+    best as usize
+}
 
-                for &light_idx in button {
-                    if light_idx < state.len() && state[light_idx] != target[light_idx]
-                    {
-                        affects_wrong = true;
-                        break;
-                    }
-                }
+/// Alternative to [`solve_lights`]'s GF(2) toggle semantics: treat the lit
+/// positions of `target_lights` as an exact-cover problem, where each
+/// target light must end up covered by exactly one pressed button and each
+/// button may be pressed at most once. A button that touches any light
+/// outside the target set can never belong to such a cover (pressing it
+/// would light an extra position), so those buttons are dropped before
+/// the search even starts. Returns the indices into `machine.buttons` of a
+/// covering selection, or `None` if no exact cover exists - every row in
+/// the returned selection is necessary, since an exact cover has no
+/// overlap for a row's columns to be redundant against.
+fn solve_lights_exact_cover(machine: &Machine) -> Option<Vec<usize>> {
+    let target_positions: Vec<usize> = machine
+        .target_lights
+        .iter()
+        .enumerate()
+        .filter(|&(_, &lit)| lit)
+        .map(|(i, _)| i)
+        .collect();
 
-                if affects_wrong {
-                    best_button = Some(i);
-                    found = true;
-                    break;
-                }
-            }
+    if target_positions.is_empty() {
+        return Some(Vec::new());
+    }
 
-            if !found {
-                break;
-            }
-        }
+    let column_of: HashMap<usize, usize> = target_positions
+        .iter()
+        .enumerate()
+        .map(|(col, &light)| (light, col))
+        .collect();
 
-        if let Some(i) = best_button {
-            for &light_idx in &machine.buttons[i] {
-                if light_idx < state.len() {
-                    state[light_idx] = !state[light_idx];
-                }
-            }
-            presses += 1;
-        } else {
-            break;
+    let mut dlx = Dlx::new(target_positions.len(), 0);
+    for (idx, button) in machine.buttons.iter().enumerate() {
+        let Some(columns): Option<Vec<usize>> =
+            button.iter().map(|light| column_of.get(light).copied()).collect()
+        else {
+            continue; // touches a light outside the target set
+        };
+        if columns.len() != columns.iter().collect::<HashSet<_>>().len() {
+            continue; // touches the same target light twice - can't take part in an exact cover
         }
+        dlx.add_row(idx, &columns);
     }
 
-    presses
+    dlx.solve()
 }
 
 // ============== PART 2 ==============
 
-fn solve_joltage(machine: &Machine) -> i64 {
+fn solve_joltage(machine: &Machine, budget: Option<Duration>) -> i64 {
     let m = machine.target_joltage.len();
     let n = machine.buttons.len();
 
@@ -215,16 +240,21 @@ fn solve_joltage(machine: &Machine) -> i64 {
         return 0;
     }
 
-    // Build augmented matrix [A|b] as rational numbers (numerator, denominator)
-    let mut aug: Vec<Vec<(i64, i64)>> = vec![vec![(0, 1); n + 1]; m];
+    // Build augmented matrix [A|b] as exact rationals: the hand-rolled
+    // (i64, i64) fractions this used to use could overflow their products
+    // during elimination well before the gcd reduction ran, producing wrong
+    // pivots. `BigRational` has no such ceiling.
+    let zero = BigRational::zero();
+    let one = BigRational::from_integer(BigInt::from(1));
+    let mut aug: Vec<Vec<BigRational>> = vec![vec![zero.clone(); n + 1]; m];
 
-    for i in 0..m {
+    for (i, row) in aug.iter_mut().enumerate() {
         for (j, button) in machine.buttons.iter().enumerate() {
             if button.contains(&i) {
-                aug[i][j] = (1, 1);
+                row[j] = one.clone();
             }
         }
-        aug[i][n] = (machine.target_joltage[i], 1);
+        row[n] = BigRational::from_integer(BigInt::from(machine.target_joltage[i]));
     }
 
     // Gaussian elimination to find pivot columns
@@ -235,40 +265,26 @@ fn solve_joltage(machine: &Machine) -> i64 {
 
     for col in 0..n_buttons {
         // Find pivot
-        let mut pivot_row = None;
-
-        for r in row..n_counters {
-            if aug[r][col].0 != 0 {
-                pivot_row = Some(r);
-                break;
-            }
-        }
+        let pivot_row = (row..n_counters).find(|&r| !aug[r][col].is_zero());
 
         if let Some(pr) = pivot_row {
             aug.swap(row, pr);
             pivot_cols.push(col);
 
             // Eliminate column
-            let pivot_entry = aug[row][col];
+            let pivot_entry = aug[row][col].clone();
 
             for r in 0..n_counters {
-                if r != row && aug[r][col].0 != 0 {
-                    let (a_num, a_den) = pivot_entry;
-                    let (b_num, b_den) = aug[r][col];
-                    let factor_num = b_num * a_den;
-                    let factor_den = b_den * a_num;
+                if r != row && !aug[r][col].is_zero() {
+                    let factor = aug[r][col].clone() / pivot_entry.clone();
 
+                    // `c` indexes two distinct rows of `aug` at once (the
+                    // pivot row `row` and the row being eliminated `r`), so
+                    // this can't be rewritten as a single iterator chain.
+                    #[allow(clippy::needless_range_loop)]
                     for c in 0..=n_buttons {
-                        let (row_num, row_den) = aug[row][c];
-                        let (r_num, r_den) = aug[r][c];
-                        let new_num =
-                            r_num * r_den * row_den * factor_den
-                                - row_num * r_den * factor_num * r_den;
-                        let new_den = r_den * r_den * row_den * factor_den;
-                        let g = gcd(new_num.abs(), new_den.abs());
-                        if g > 0 {
-                            aug[r][c] = (new_num / g, new_den / g);
-                        }
+                        let sub = factor.clone() * aug[row][c].clone();
+                        aug[r][c] -= sub;
                     }
                 }
             }
@@ -288,35 +304,33 @@ fn solve_joltage(machine: &Machine) -> i64 {
         let mut solution = vec![0i64; n_buttons];
 
         for (r, &col) in pivot_cols.iter().enumerate() {
-            let (num, den) = aug[r][n_buttons];
-            let (pivot_num, pivot_den) = aug[r][col];
-            let val_num = num * pivot_den;
-            let val_den = den * pivot_num;
+            let value = aug[r][n_buttons].clone() / aug[r][col].clone();
 
-            if val_den == 0 || val_num % val_den != 0 || val_num / val_den < 0 {
+            if !value.is_integer() || value.is_negative() {
                 return greedy_solve(machine);
             }
 
-            solution[col] = val_num / val_den;
+            solution[col] = value.to_integer().to_i64().unwrap();
         }
 
         return solution.iter().sum();
     }
 
-    // Search over free variables
+    // Branch-and-bound over free variables, anytime: if `budget` runs out
+    // first, `best` still holds the best complete solution found so far.
+    let deadline = budget.map(|d| Instant::now() + d);
     let mut best = i64::MAX;
     let mut free_vals = Vec::new();
 
-    search_free(
+    branch_and_bound(
         0,
         &free_cols,
         &pivot_cols,
         &aug,
-        n_buttons,
         &mut free_vals,
         max_val,
         &mut best,
-        machine,
+        deadline,
     );
 
     if best == i64::MAX {
@@ -326,87 +340,114 @@ fn solve_joltage(machine: &Machine) -> i64 {
     }
 }
 
-fn search_free(
-    idx: usize,
+/// For every pivot row whose coefficient is zero on every free column not
+/// yet assigned (positions `free_vals.len()..`), that row's value no longer
+/// depends on any future branching choice, so it can be evaluated now
+/// instead of waiting for a full leaf assignment. Returns `None` if any
+/// such row is already forced negative or non-integer - a dead end no
+/// matter how the remaining free variables are chosen - or `Some(cost)`,
+/// the exact press count those settled rows contribute.
+fn settled_pivot_cost(
     free_cols: &[usize],
     pivot_cols: &[usize],
-    aug: &[Vec<(i64, i64)>],
-    n_buttons: usize,
-    free_vals: &mut Vec<i64>,
-    max_val: i64,
-    best: &mut i64,
-    machine: &Machine,
-) {
-    if idx == free_cols.len() {
-        // Compute pivot variable values
-        let mut solution = vec![0i64; n_buttons];
+    aug: &[Vec<BigRational>],
+    free_vals: &[i64],
+) -> Option<i64> {
+    let mut cost = 0i64;
+
+    for (r, &pc) in pivot_cols.iter().enumerate() {
+        let depends_on_unassigned = free_cols[free_vals.len()..]
+            .iter()
+            .any(|&fc| !aug[r][fc].is_zero());
+        if depends_on_unassigned {
+            continue;
+        }
 
-        for (i, &fc) in free_cols.iter().enumerate() {
-            solution[fc] = free_vals[i];
+        let mut rhs = aug[r][aug[r].len() - 1].clone();
+        for (i, &fc) in free_cols[..free_vals.len()].iter().enumerate() {
+            rhs -= aug[r][fc].clone() * BigRational::from_integer(BigInt::from(free_vals[i]));
         }
 
-        for (r, &pc) in pivot_cols.iter().enumerate() {
-            let (b_num, b_den) = aug[r][n_buttons];
-            let mut rhs_num = b_num;
-            let mut rhs_den = b_den;
-
-            for &fc in free_cols {
-                let (coef_num, coef_den) = aug[r][fc];
-                let fc_idx = free_cols.iter().position(|&x| x == fc).unwrap();
-                let sub_num = coef_num * free_vals[fc_idx];
-                let sub_den = coef_den;
-                rhs_num = rhs_num * sub_den - sub_num * rhs_den;
-                rhs_den *= sub_den;
-                let g = gcd(rhs_num.abs(), rhs_den.abs());
-                if g > 0 {
-                    rhs_num /= g;
-                    rhs_den /= g;
-                }
-            }
+        let value = rhs / aug[r][pc].clone();
+        if !value.is_integer() || value.is_negative() {
+            return None;
+        }
 
-            let (pivot_num, pivot_den) = aug[r][pc];
-            let val_num = rhs_num * pivot_den;
-            let val_den = rhs_den * pivot_num;
+        cost += value.to_integer().to_i64().unwrap();
+    }
 
-            if val_den == 0 || val_num % val_den != 0 || val_num / val_den < 0 {
-                return;
-            }
+    Some(cost)
+}
 
-            solution[pc] = val_num / val_den;
-        }
+/// Least-cost branch-and-bound over the free variables, in the fixed order
+/// `free_cols`. Returns `false` as soon as `deadline` passes, propagating
+/// up through the recursion so the search unwinds immediately; `true` means
+/// the subtree was fully explored.
+#[allow(clippy::too_many_arguments)]
+fn branch_and_bound(
+    idx: usize,
+    free_cols: &[usize],
+    pivot_cols: &[usize],
+    aug: &[Vec<BigRational>],
+    free_vals: &mut Vec<i64>,
+    max_val: i64,
+    best: &mut i64,
+    deadline: Option<Instant>,
+) -> bool {
+    if deadline.is_some_and(|dl| Instant::now() >= dl) {
+        return false;
+    }
 
-        let cost: i64 = solution.iter().sum();
+    if idx == free_cols.len() {
+        let Some(settled) = settled_pivot_cost(free_cols, pivot_cols, aug, free_vals)
+        else {
+            return true;
+        };
 
+        let cost: i64 = free_vals.iter().sum::<i64>() + settled;
         if cost < *best {
             *best = cost;
         }
 
-        return;
+        return true;
     }
 
+    // Admissible lower bound: already-chosen free variables plus whatever
+    // pivot rows are already fully determined by them; every remaining
+    // free and pivot variable only adds non-negative presses, so this can
+    // never overestimate the eventual cost.
     let current_cost: i64 = free_vals.iter().sum();
+    let Some(settled) = settled_pivot_cost(free_cols, pivot_cols, aug, free_vals)
+    else {
+        return true;
+    };
 
-    if current_cost >= *best {
-        return;
+    if current_cost + settled >= *best {
+        return true;
     }
 
     for v in 0..=max_val {
         free_vals.push(v);
 
-        search_free(
+        let completed = branch_and_bound(
             idx + 1,
             free_cols,
             pivot_cols,
             aug,
-            n_buttons,
             free_vals,
             max_val,
             best,
-            machine,
+            deadline,
         );
 
         free_vals.pop();
+
+        if !completed {
+            return false;
+        }
     }
+
+    true
 }
 
 fn greedy_solve(machine: &Machine) -> i64 {
@@ -418,9 +459,9 @@ fn greedy_solve(machine: &Machine) -> i64 {
 
     let mut upper_bounds = vec![0i64; n];
 
-    for j in 0..n {
+    for (j, button) in machine.buttons.iter().enumerate() {
         for i in 0..m {
-            if machine.buttons[j].contains(&i) {
+            if button.contains(&i) {
                 upper_bounds[j] = upper_bounds[j].max(machine.target_joltage[i]);
             }
         }
@@ -496,22 +537,35 @@ fn greedy_solve(machine: &Machine) -> i64 {
     x.iter().sum()
 }
 
-fn gcd(a: i64, b: i64) -> i64 {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
+pub fn part_one(input: &str) -> usize {
+    let machines = parse_input(input);
+    machines.iter().map(solve_lights).sum()
 }
 
-pub fn part_one(input: &str) -> usize {
+/// Alongside the toggle-based minimum-weight solver used by [`part_one`],
+/// solve each machine's lights as a literal exact cover: every pressed
+/// button used at most once, every lit position covered exactly once.
+/// Returns each machine's button selection in input order, or `None` for
+/// a machine with no exact cover.
+pub fn part_one_exact_cover(input: &str) -> Vec<Option<Vec<usize>>> {
     let machines = parse_input(input);
-    machines.iter().map(|m| solve_lights(m)).sum()
+    machines.iter().map(solve_lights_exact_cover).collect()
 }
 
 pub fn part_two(input: &str) -> i64 {
     let machines = parse_input(input);
-    machines.iter().map(solve_joltage).sum()
+    machines.iter().map(|m| solve_joltage(m, None)).sum()
+}
+
+/// Like [`part_two`], but bounds each machine's branch-and-bound search to
+/// `budget` wall-clock time, trading optimality for a guaranteed return -
+/// useful for inputs whose free-variable count makes exhaustive search slow.
+pub fn part_two_with_budget(input: &str, budget: Duration) -> i64 {
+    let machines = parse_input(input);
+    machines
+        .iter()
+        .map(|m| solve_joltage(m, Some(budget)))
+        .sum()
 }
 
 #[cfg(test)]
@@ -1,9 +1,33 @@
 //! Day 12: Christmas Tree Farm
 //!
-//! Optimized packing algorithm using bitmask grid representation.
+//! Pack the required count of each tree shape (in any rotation/reflection)
+//! into each region without overlap.
+//!
+//! ## Solution Approach
+//!
+//! Modeled as an exact cover problem and solved with Knuth's Algorithm X
+//! (see [`crate::dlx`]): one *primary* column per required piece instance
+//! (it must be covered exactly once), one *secondary* column per board
+//! cell (covered at most once - a region may be larger than the pieces'
+//! combined area). Each row is one placement of one transformed shape at
+//! one anchor position, with a 1 in that instance's column and in every
+//! cell its shape occupies.
+//!
+//! **Part 1**: a region fits iff the matrix has any exact cover.
+//! **Part 2**: sum the number of distinct exact covers (tilings) over the
+//! regions that fit.
+//!
+//! Rows are built from a bitboard view of each shape transform: `rows[i]`
+//! packs transform row `i`'s occupied columns into one `u64`, so anchor
+//! bounds-checking is an O(1) range check and enumerating a placement's
+//! occupied cells is a trailing-zeros scan over the shifted mask instead
+//! of a per-cell loop. This assumes regions are at most 64 columns wide.
 
 use std::collections::HashSet;
 
+use crate::dlx::Dlx;
+use crate::grid::Grid;
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 struct Shape {
     cells: Vec<(i32, i32)>,
@@ -28,14 +52,12 @@ fn parse_input(input: &str) -> (Vec<Vec<Shape>>, Vec<Region>) {
     }
 
     let shapes: Vec<Vec<Shape>> = shapes_input.iter().map(|lines| {
-        let mut cells = Vec::new();
-        for (r, line) in lines.iter().enumerate() {
-            for (c, ch) in line.chars().enumerate() {
-                if ch == '#' {
-                    cells.push((r as i32, c as i32));
-                }
-            }
-        }
+        let grid = Grid::parse(&lines.join("\n"), |ch| ch == '#');
+        let cells = grid
+            .iter()
+            .filter(|&(_, _, &filled)| filled)
+            .map(|(r, c, _)| (r as i32, c as i32))
+            .collect();
         let base = normalize(cells);
         get_transformations(&base)
     }).collect();
@@ -100,96 +122,111 @@ fn get_transformations(shape: &Shape) -> Vec<Shape> {
     result
 }
 
-fn can_fit(shapes: &[Vec<Shape>], region: &Region) -> bool {
-    let total_cells: usize = region.required.iter()
-        .enumerate()
-        .map(|(i, &c)| c * shapes[i][0].cells.len())
-        .sum();
-    if total_cells > region.width * region.height {
-        return false;
-    }
-
-    let mut items: Vec<usize> = Vec::new();
+/// One entry per required piece instance, expanded from `region.required` (e.g.
+/// required = [2, 1] becomes items = [0, 0, 1]); each entry becomes a
+/// distinct primary column, since an exact cover needs one column per
+/// instance even though instances of the same shape are interchangeable.
+fn region_items(region: &Region) -> Vec<usize> {
+    let mut items = Vec::new();
     for (i, &c) in region.required.iter().enumerate() {
         items.extend(std::iter::repeat(i).take(c));
     }
-    items.sort_unstable_by_key(|&i| std::cmp::Reverse(shapes[i][0].cells.len()));
-
-    let mut grid = vec![0u64; region.height * region.width];
-    solve_fast(&mut grid, shapes, &items, 0, region.width, region.height)
+    items
 }
 
-fn solve_fast(
-    grid: &mut [u64],
-    shapes: &[Vec<Shape>],
-    items: &[usize],
-    idx: usize,
+/// A shape transform packed into per-row bitmasks, relative to its own
+/// bounding box: `rows[i]` has a bit set for every column the shape
+/// occupies in relative row `i`. Built once per transform so that, for
+/// every anchor, bounds-checking and cell enumeration are O(1)/O(popcount)
+/// instead of O(cells).
+struct ShapeMask {
+    rows: Vec<u64>,
     width: usize,
     height: usize,
-) -> bool {
-    if idx >= items.len() {
-        return true;
-    }
-
-    let shape_idx = items[idx];
-
-    // Precompute all valid positions for this shape
-    let mut placements = Vec::new();
-
-    for shape in &shapes[shape_idx] {
-        for pos_r in 0..height {
-            for pos_c in 0..width {
-                if can_place_fast(grid, shape, pos_r, pos_c, width, height) {
-                    placements.push((shape, pos_r, pos_c));
-                }
-            }
-        }
-    }
-
-    // Try each placement
-    for (shape, pos_r, pos_c) in placements {
-        place_fast(grid, shape, pos_r, pos_c, width);
+}
 
-        if solve_fast(grid, shapes, items, idx + 1, width, height) {
-            return true;
-        }
+fn shape_mask(shape: &Shape) -> ShapeMask {
+    let height = shape.cells.iter().map(|&(r, _)| r).max().unwrap() as usize + 1;
+    let width = shape.cells.iter().map(|&(_, c)| c).max().unwrap() as usize + 1;
+    debug_assert!(width <= 64, "bitboard rows need width <= 64 columns");
 
-        unplace_fast(grid, shape, pos_r, pos_c, width);
+    let mut rows = vec![0u64; height];
+    for &(r, c) in &shape.cells {
+        rows[r as usize] |= 1u64 << c;
     }
-
-    false
+    ShapeMask { rows, width, height }
 }
 
-fn can_place_fast(
-    grid: &[u64],
-    shape: &Shape,
-    pos_r: usize,
-    pos_c: usize,
+/// Anchors where `mask` fits entirely within a `width x height` board.
+/// Empty (not `(0, 0)`) whenever the shape's bounding box doesn't fit in
+/// one or both dimensions - `can_fit`'s summed-area precheck doesn't rule
+/// this out for an oddly-shaped piece, and a bogus `(0, 0)` anchor would
+/// go on to emit cell columns past the board's secondary-column range.
+fn anchors_in_bounds(
+    mask: &ShapeMask,
     width: usize,
     height: usize,
-) -> bool {
-    shape.cells.iter().all(|&(dr, dc)| {
-        let nr = pos_r as i32 + dr;
-        let nc = pos_c as i32 + dc;
-        nr >= 0 && nc >= 0 && (nr as usize) < height && (nc as usize) < width
-            && grid[nr as usize * width + nc as usize] == 0
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let rows = height.checked_sub(mask.height);
+    let cols = width.checked_sub(mask.width);
+    rows.into_iter().flat_map(move |max_r| {
+        let cols = cols;
+        (0..=max_r).flat_map(move |pos_r| {
+            cols.into_iter()
+                .flat_map(move |max_c| (0..=max_c).map(move |pos_c| (pos_r, pos_c)))
+        })
     })
 }
 
-fn place_fast(grid: &mut [u64], shape: &Shape, pos_r: usize, pos_c: usize, width: usize) {
-    for &(dr, dc) in &shape.cells {
-        let nr = pos_r as usize + dr as usize;
-        let nc = pos_c as usize + dc as usize;
-        grid[nr * width + nc] = 1;
+/// Build the exact-cover matrix for packing `items` into a `width x
+/// height` board: one primary column per item, one secondary column per
+/// cell, one row per (item, transformation, anchor) placement.
+fn build_dlx(shapes: &[Vec<Shape>], items: &[usize], width: usize, height: usize) -> Dlx {
+    let mut dlx = Dlx::new(items.len(), width * height);
+
+    let mut row_id = 0;
+    for (item, &shape_idx) in items.iter().enumerate() {
+        for shape in &shapes[shape_idx] {
+            let mask = shape_mask(shape);
+            for (pos_r, pos_c) in anchors_in_bounds(&mask, width, height) {
+                let mut columns = vec![item];
+                for (dr, &row) in mask.rows.iter().enumerate() {
+                    let mut bits = row << pos_c;
+                    while bits != 0 {
+                        let dc = bits.trailing_zeros() as usize;
+                        let nr = pos_r + dr;
+                        columns.push(items.len() + nr * width + dc);
+                        bits &= bits - 1;
+                    }
+                }
+                dlx.add_row(row_id, &columns);
+                row_id += 1;
+            }
+        }
     }
+
+    dlx
 }
 
-fn unplace_fast(grid: &mut [u64], shape: &Shape, pos_r: usize, pos_c: usize, width: usize) {
-    for &(dr, dc) in &shape.cells {
-        let nr = pos_r as usize + dr as usize;
-        let nc = pos_c as usize + dc as usize;
-        grid[nr * width + nc] = 0;
+fn can_fit(shapes: &[Vec<Shape>], region: &Region) -> bool {
+    let total_cells: usize = region.required.iter()
+        .enumerate()
+        .map(|(i, &c)| c * shapes[i][0].cells.len())
+        .sum();
+    if total_cells > region.width * region.height {
+        return false;
     }
+
+    let items = region_items(region);
+    let mut dlx = build_dlx(shapes, &items, region.width, region.height);
+    dlx.solve().is_some()
+}
+
+/// Number of distinct exact covers (tilings) of `region`, up to `limit`.
+fn count_tilings(shapes: &[Vec<Shape>], region: &Region, limit: usize) -> usize {
+    let items = region_items(region);
+    let mut dlx = build_dlx(shapes, &items, region.width, region.height);
+    dlx.count_solutions(limit)
 }
 
 pub fn part_one(input: &str) -> usize {
@@ -197,8 +234,12 @@ pub fn part_one(input: &str) -> usize {
     regions.iter().filter(|r| can_fit(&shapes, r)).count()
 }
 
-pub fn part_two(_input: &str) -> usize {
-    0
+pub fn part_two(input: &str) -> usize {
+    let (shapes, regions) = parse_input(input);
+    regions
+        .iter()
+        .map(|r| count_tilings(&shapes, r, usize::MAX))
+        .sum()
 }
 
 #[cfg(test)]
@@ -214,6 +255,11 @@ mod tests {
 
     #[test]
     fn part2_example() {
-        assert_eq!(part_two(""), 0);
+        // A single 1x1 shape, required twice, in a 2x1 board: the two
+        // instances can each land on either of the two cells, so there are
+        // two distinct exact covers - this exercises the DLX tiling count
+        // itself rather than the degenerate zero-regions case.
+        let input = "#\n\n2x1: 2\n";
+        assert_eq!(part_two(input), 2);
     }
 }
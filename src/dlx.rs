@@ -0,0 +1,235 @@
+//! Knuth's "Dancing Links" (Algorithm X) for the exact cover problem.
+//!
+//! Columns are split into *primary* columns, which a solution must cover
+//! exactly once, and *secondary* columns, which a solution may cover at
+//! most once but never has to - secondary columns are never chosen as a
+//! branching column. This is the standard extension used whenever some
+//! resource (e.g. a board cell) is allowed to stay unused.
+//!
+//! The matrix is a sparse grid of circular doubly-linked lists (one node
+//! per 1-entry), navigated by index into a single `Vec<Node>` rather than
+//! raw pointers.
+
+const ROOT: usize = 0;
+
+#[derive(Clone, Copy)]
+struct Node {
+    left: usize,
+    right: usize,
+    up: usize,
+    down: usize,
+    column: usize,
+}
+
+/// An exact-cover matrix ready for Algorithm X search.
+pub struct Dlx {
+    nodes: Vec<Node>,
+    size: Vec<usize>,
+    row_of: Vec<usize>,
+    solution: Vec<usize>,
+}
+
+impl Dlx {
+    /// Create an empty matrix with `num_primary` primary columns followed
+    /// by `num_secondary` secondary columns (column indices passed to
+    /// `add_row` are 0-based over `0..num_primary + num_secondary`).
+    pub fn new(num_primary: usize, num_secondary: usize) -> Self {
+        let num_columns = num_primary + num_secondary;
+        let mut nodes = Vec::with_capacity(num_columns + 1);
+        nodes.push(Node { left: 0, right: 0, up: 0, down: 0, column: 0 });
+        for c in 1..=num_columns {
+            nodes.push(Node { left: c, right: c, up: c, down: c, column: c });
+        }
+
+        // Only primary columns are linked into the root's horizontal ring;
+        // secondary columns stay self-linked, so they're never visited by
+        // `choose_column` and never chosen as a branch column.
+        for c in 1..=num_primary {
+            let root_left = nodes[ROOT].left;
+            nodes[root_left].right = c;
+            nodes[c].left = root_left;
+            nodes[c].right = ROOT;
+            nodes[ROOT].left = c;
+        }
+
+        Dlx {
+            nodes,
+            size: vec![0; num_columns + 1],
+            row_of: vec![usize::MAX; num_columns + 1],
+            solution: Vec::new(),
+        }
+    }
+
+    /// Add a row with a 1 in each of `columns`, tagged with `row_id` so a
+    /// solution can report which rows it chose.
+    pub fn add_row(&mut self, row_id: usize, columns: &[usize]) {
+        let mut first: Option<usize> = None;
+        let mut prev: Option<usize> = None;
+
+        for &col in columns {
+            let c = col + 1;
+            let idx = self.nodes.len();
+            let up = self.nodes[c].up;
+            self.nodes.push(Node { left: idx, right: idx, up, down: c, column: c });
+            self.nodes[up].down = idx;
+            self.nodes[c].up = idx;
+            self.size[c] += 1;
+            self.row_of.push(row_id);
+
+            if let Some(p) = prev {
+                self.nodes[p].right = idx;
+                self.nodes[idx].left = p;
+            } else {
+                first = Some(idx);
+            }
+            prev = Some(idx);
+        }
+
+        if let (Some(f), Some(p)) = (first, prev) {
+            self.nodes[p].right = f;
+            self.nodes[f].left = p;
+        }
+    }
+
+    /// Column with the fewest remaining rows (the minimum-remaining-values
+    /// heuristic), or `None` if every primary column is already covered.
+    fn choose_column(&self) -> Option<usize> {
+        let mut c = self.nodes[ROOT].right;
+        if c == ROOT {
+            return None;
+        }
+
+        let mut best = c;
+        let mut best_size = self.size[c];
+        while c != ROOT {
+            if self.size[c] < best_size {
+                best = c;
+                best_size = self.size[c];
+            }
+            c = self.nodes[c].right;
+        }
+        Some(best)
+    }
+
+    fn cover(&mut self, c: usize) {
+        let l = self.nodes[c].left;
+        let r = self.nodes[c].right;
+        self.nodes[l].right = r;
+        self.nodes[r].left = l;
+
+        let mut i = self.nodes[c].down;
+        while i != c {
+            let mut j = self.nodes[i].right;
+            while j != i {
+                let u = self.nodes[j].up;
+                let d = self.nodes[j].down;
+                self.nodes[u].down = d;
+                self.nodes[d].up = u;
+                self.size[self.nodes[j].column] -= 1;
+                j = self.nodes[j].right;
+            }
+            i = self.nodes[i].down;
+        }
+    }
+
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.nodes[c].up;
+        while i != c {
+            let mut j = self.nodes[i].left;
+            while j != i {
+                self.size[self.nodes[j].column] += 1;
+                let u = self.nodes[j].up;
+                let d = self.nodes[j].down;
+                self.nodes[u].down = j;
+                self.nodes[d].up = j;
+                j = self.nodes[j].left;
+            }
+            i = self.nodes[i].up;
+        }
+
+        let l = self.nodes[c].left;
+        let r = self.nodes[c].right;
+        self.nodes[l].right = c;
+        self.nodes[r].left = c;
+    }
+
+    /// Find one exact cover, returning the row ids it chose.
+    pub fn solve(&mut self) -> Option<Vec<usize>> {
+        if self.search() {
+            Some(self.solution.iter().map(|&n| self.row_of[n]).collect())
+        } else {
+            None
+        }
+    }
+
+    fn search(&mut self) -> bool {
+        let Some(c) = self.choose_column() else {
+            return true;
+        };
+        if self.size[c] == 0 {
+            return false;
+        }
+
+        self.cover(c);
+        let mut r = self.nodes[c].down;
+        while r != c {
+            self.solution.push(r);
+            let mut j = self.nodes[r].right;
+            while j != r {
+                self.cover(self.nodes[j].column);
+                j = self.nodes[j].right;
+            }
+
+            if self.search() {
+                return true;
+            }
+
+            self.solution.pop();
+            let mut j = self.nodes[r].left;
+            while j != r {
+                self.uncover(self.nodes[j].column);
+                j = self.nodes[j].left;
+            }
+            r = self.nodes[r].down;
+        }
+        self.uncover(c);
+        false
+    }
+
+    /// Count exact covers, stopping early once `limit` have been found
+    /// (pass `usize::MAX` for an exhaustive count).
+    pub fn count_solutions(&mut self, limit: usize) -> usize {
+        self.count_search(limit)
+    }
+
+    fn count_search(&mut self, limit: usize) -> usize {
+        let Some(c) = self.choose_column() else {
+            return 1;
+        };
+        if self.size[c] == 0 {
+            return 0;
+        }
+
+        let mut count = 0;
+        self.cover(c);
+        let mut r = self.nodes[c].down;
+        while r != c && count < limit {
+            let mut j = self.nodes[r].right;
+            while j != r {
+                self.cover(self.nodes[j].column);
+                j = self.nodes[j].right;
+            }
+
+            count += self.count_search(limit - count);
+
+            let mut j = self.nodes[r].left;
+            while j != r {
+                self.uncover(self.nodes[j].column);
+                j = self.nodes[j].left;
+            }
+            r = self.nodes[r].down;
+        }
+        self.uncover(c);
+        count
+    }
+}
@@ -0,0 +1,82 @@
+//! Fetches puzzle inputs and example blocks from adventofcode.com, caching
+//! them under `inputs/` so later runs never touch the network. Only
+//! compiled when the `fetch` feature is enabled, keeping offline builds
+//! free of the `ureq` dependency.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const YEAR: u16 = 2025;
+
+/// Fetch a `day`/`filename` puzzle file and cache it for next time. Only
+/// reached (through `read_input` / `read_example` below) from
+/// `lib.rs::read_as_string` once it's already confirmed the file is
+/// missing, so this doesn't re-check for it.
+fn fetch_and_cache(day: u8, filename: &str, fetch: impl Fn(u8) -> String) -> String {
+    let path = format!("inputs/{day:02}-{filename}.txt");
+
+    let contents = fetch(day);
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir).unwrap();
+    }
+    fs::write(&path, &contents).unwrap();
+    contents
+}
+
+/// Fetch and cache the puzzle input for `day`. Called once `lib.rs` has
+/// already determined the cached file is missing.
+pub fn read_input(day: u8) -> String {
+    fetch_and_cache(day, "input", fetch_input)
+}
+
+/// Fetch and cache the example for `day`. Called once `lib.rs` has
+/// already determined the cached file is missing.
+pub fn read_example(day: u8) -> String {
+    fetch_and_cache(day, "example", fetch_example)
+}
+
+/// Read the session cookie used to authenticate with adventofcode.com.
+fn session_cookie() -> String {
+    env::var("AOC_SESSION")
+        .expect("AOC_SESSION must be set to fetch puzzle data")
+}
+
+/// Download the puzzle input for `day`.
+pub fn fetch_input(day: u8) -> String {
+    let url = format!("https://adventofcode.com/{YEAR}/day/{day}/input");
+    ureq::get(&url)
+        .set("Cookie", &format!("session={}", session_cookie()))
+        .call()
+        .unwrap()
+        .into_string()
+        .unwrap()
+}
+
+/// Download the puzzle page for `day` and scrape the first example block.
+pub fn fetch_example(day: u8) -> String {
+    let url = format!("https://adventofcode.com/{YEAR}/day/{day}");
+    let page = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session_cookie()))
+        .call()
+        .unwrap()
+        .into_string()
+        .unwrap();
+
+    first_code_block(&page)
+        .unwrap_or_else(|| panic!("no <pre><code> block found on day {day} page"))
+}
+
+/// Extract and unescape the contents of the first `<pre><code>` block.
+fn first_code_block(html: &str) -> Option<String> {
+    let start = html.find("<pre><code>")? + "<pre><code>".len();
+    let end = html[start..].find("</code></pre>")? + start;
+
+    let block = html[start..end]
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&");
+
+    Some(format!("{}\n", block.trim_end()))
+}
@@ -0,0 +1,108 @@
+//! Shared 2D character-grid parsing. Several days (6, 7, 12) need
+//! row/column indexing, neighbor lookups, and digit scanning over a grid
+//! of characters; this module provides that as one parsed `Grid<T>` plus a
+//! `scan_numbers` combinator, so coordinate handling lives in one place.
+
+/// A dense, possibly-ragged 2D grid of parsed cells, addressed by
+/// `(row, col)` in row-major order.
+pub struct Grid<T> {
+    cells: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+    /// Parse `input` line by line, calling `parse_cell` once per
+    /// character. Blank lines are skipped; rows may have different
+    /// lengths, and a column past the end of a shorter row is simply
+    /// absent rather than an error.
+    pub fn parse(input: &str, parse_cell: impl Fn(char) -> T) -> Self {
+        let cells = input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().map(&parse_cell).collect())
+            .collect();
+        Grid { cells }
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The longest row's length.
+    pub fn width(&self) -> usize {
+        self.cells.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        self.cells.get(row).map(Vec::as_slice)
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        self.cells.get(row)?.get(col)
+    }
+
+    /// Every `(row, col, &cell)` in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> {
+        self.cells.iter().enumerate().flat_map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(move |(col, cell)| (row, col, cell))
+        })
+    }
+
+    /// The first cell matching `predicate`, in row-major order.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|&(_, _, cell)| predicate(cell))
+            .map(|(row, col, _)| (row, col))
+    }
+
+    pub fn up(&self, row: usize, col: usize) -> Option<&T> {
+        row.checked_sub(1).and_then(|r| self.get(r, col))
+    }
+
+    pub fn down(&self, row: usize, col: usize) -> Option<&T> {
+        self.get(row + 1, col)
+    }
+
+    pub fn left(&self, row: usize, col: usize) -> Option<&T> {
+        col.checked_sub(1).and_then(|c| self.get(row, c))
+    }
+
+    pub fn right(&self, row: usize, col: usize) -> Option<&T> {
+        self.get(row, col + 1)
+    }
+
+    /// Column `col` read top-to-bottom, skipping rows too short to have it.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().filter_map(move |row| row.get(col))
+    }
+}
+
+/// Scan a row of characters for runs of ASCII digits, yielding each run's
+/// parsed value paired with the column it starts at.
+pub fn scan_numbers(row: &[char]) -> Vec<(u128, usize)> {
+    let mut numbers = Vec::new();
+    let mut col = 0;
+
+    while col < row.len() {
+        while col < row.len() && !row[col].is_ascii_digit() {
+            col += 1;
+        }
+        if col >= row.len() {
+            break;
+        }
+
+        let start = col;
+        while col < row.len() && row[col].is_ascii_digit() {
+            col += 1;
+        }
+
+        let text: String = row[start..col].iter().collect();
+        if let Ok(value) = text.parse() {
+            numbers.push((value, start));
+        }
+    }
+
+    numbers
+}
@@ -9,10 +9,46 @@ pub mod day05;
 pub mod day06;
 pub mod day07;
 pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day12;
 
+pub mod cycle;
+pub mod dlx;
+pub mod grid;
+pub mod vm;
+
+#[cfg(feature = "fetch")]
+mod fetch;
+
+/// Read a cached puzzle file, fetching and caching it first if it's missing.
+///
+/// Without the `fetch` feature, a missing file is still a hard error: this
+/// keeps offline builds free of the network dependency, and CI without an
+/// `AOC_SESSION` cookie still works off whatever is already committed.
 pub fn read_as_string(day: u8, filename: &str) -> String {
-    let filename = format!("inputs/{day:02}-{filename}.txt");
-    fs::read_to_string(filename).unwrap()
+    let path = format!("inputs/{day:02}-{filename}.txt");
+
+    if let Ok(contents) = fs::read_to_string(&path) {
+        return contents;
+    }
+
+    #[cfg(feature = "fetch")]
+    {
+        if filename == "example" {
+            fetch::read_example(day)
+        } else {
+            fetch::read_input(day)
+        }
+    }
+
+    #[cfg(not(feature = "fetch"))]
+    {
+        panic!(
+            "missing {path}; enable the `fetch` feature (and set AOC_SESSION) \
+             or provide the file"
+        )
+    }
 }
 
 pub fn read_input(day: u8) -> String {
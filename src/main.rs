@@ -1,11 +1,44 @@
 use std::env;
 use std::fmt::Display;
+use std::ops::RangeInclusive;
 use std::time::SystemTime;
 
+/// Parse day selection arguments into a sorted, deduplicated list, keeping
+/// only days that are actually registered.
+///
+/// Accepts bare integers (`7`), inclusive ranges (`1..=3`, `1-3`), and
+/// comma-separated lists of either (`1,3,7`), freely mixed across args.
+fn parse_days(args: &[String], registered: &[u8]) -> Vec<usize> {
+    let mut days: Vec<usize> = args
+        .iter()
+        .flat_map(|arg| arg.split(','))
+        .flat_map(|part| match parse_day_range(part.trim()) {
+            Some(range) => range.collect::<Vec<usize>>(),
+            None => part.trim().parse().ok().into_iter().collect(),
+        })
+        .filter(|&day| u8::try_from(day).is_ok_and(|day| registered.contains(&day)))
+        .collect();
+
+    days.sort_unstable();
+    days.dedup();
+    days
+}
+
+/// Parse `"1..=3"` or `"1-3"` into an inclusive range.
+fn parse_day_range(part: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = part
+        .split_once("..=")
+        .or_else(|| part.split_once('-'))?;
+    let start: usize = start.trim().parse().ok()?;
+    let end: usize = end.trim().parse().ok()?;
+    Some(start..=end)
+}
+
 fn main() {
     macro_rules! puzzle {
-        ($mod:ident, $title:expr) => {
+        ($mod:ident, $day:expr, $title:expr) => {
             (
+                $day,
                 $title,
                 |input| Box::new(aoc::$mod::part_one(input)),
                 |input| Box::new(aoc::$mod::part_two(input)),
@@ -15,12 +48,16 @@ fn main() {
 
     type SolverFn = fn(&str) -> Box<dyn Display>;
 
-    let puzzles: Vec<(&str, SolverFn, SolverFn)> = vec![
+    let puzzles: Vec<(u8, &str, SolverFn, SolverFn)> = vec![
         // register puzzle here
-        puzzle!(day01, "Secret Entrance"),
-        puzzle!(day02, "Gift Shop"),
-        puzzle!(day03, "Lobby"),
-        // puzzle!(day00, "Template"),  // Uncomment and update when solving day
+        puzzle!(day01, 1, "Secret Entrance"),
+        puzzle!(day02, 2, "Gift Shop"),
+        puzzle!(day03, 3, "Lobby"),
+        puzzle!(day08, 8, "Playground"),
+        puzzle!(day09, 9, "Movie Theater"),
+        puzzle!(day10, 10, "Factory"),
+        puzzle!(day12, 12, "Christmas Tree Farm"),
+        // puzzle!(day00, 0, "Template"),  // Uncomment and update when solving day
     ];
 
     let filename = match env::args().find(|a| a == "--example") {
@@ -30,15 +67,21 @@ fn main() {
 
     let show_time = env::args().any(|a| a == "--time");
 
-    let mut days: Vec<usize> =
-        env::args().filter_map(|a| a.parse().ok()).collect();
+    let registered: Vec<u8> = puzzles.iter().map(|&(day, ..)| day).collect();
+    let day_args: Vec<String> =
+        env::args().skip(1).filter(|a| !a.starts_with("--")).collect();
+    let mut days = parse_days(&day_args, &registered);
 
     if days.is_empty() {
-        days = (1..=puzzles.len()).collect();
+        days = registered.iter().map(|&day| day as usize).collect();
+        days.sort_unstable();
     }
 
     for day in days {
-        let (title, part1, part2) = &puzzles[day - 1];
+        let (_, title, part1, part2) = puzzles
+            .iter()
+            .find(|&&(registered_day, ..)| registered_day as usize == day)
+            .unwrap();
         let input = aoc::read_as_string(day as u8, filename);
         let input = input.as_str();
 
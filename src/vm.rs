@@ -0,0 +1,114 @@
+//! Handheld-console VM subsystem for assembly-style puzzles: a tiny
+//! instruction set with an executor that detects infinite loops, plus the
+//! classic "flip one `jmp`/`nop`" repair search used by part two.
+
+use std::collections::HashSet;
+
+/// A single instruction. Extend with more variants as future days need them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Acc(isize),
+    Jmp(isize),
+    Nop(isize),
+}
+
+/// Parse lines like `acc +3` / `jmp -4` / `nop +0` into a program.
+pub fn parse(input: &str) -> Vec<Op> {
+    input
+        .trim()
+        .lines()
+        .map(|line| {
+            let (op, arg) = line.trim().split_once(' ').unwrap();
+            let arg: isize = arg.parse().unwrap();
+            match op {
+                "acc" => Op::Acc(arg),
+                "jmp" => Op::Jmp(arg),
+                "nop" => Op::Nop(arg),
+                other => panic!("unknown instruction: {other}"),
+            }
+        })
+        .collect()
+}
+
+/// The outcome of running a program to completion or until it loops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunResult {
+    Loop(isize),
+    Finish(isize),
+}
+
+/// Execute `program`, returning `Loop(acc)` the moment an instruction would
+/// run a second time, or `Finish(acc)` once the pointer steps past the end.
+pub fn run(program: &[Op]) -> RunResult {
+    let mut executed: HashSet<usize> = HashSet::new();
+    let mut acc: isize = 0;
+    let mut ip: isize = 0;
+
+    loop {
+        if ip as usize >= program.len() {
+            return RunResult::Finish(acc);
+        }
+        if !executed.insert(ip as usize) {
+            return RunResult::Loop(acc);
+        }
+
+        match program[ip as usize] {
+            Op::Acc(n) => {
+                acc += n;
+                ip += 1;
+            }
+            Op::Jmp(n) => ip += n,
+            Op::Nop(_) => ip += 1,
+        }
+    }
+}
+
+/// Try swapping each `Jmp`<->`Nop` in turn and return the accumulator of the
+/// first mutation that finishes instead of looping — the classic part-two fix.
+pub fn fix_and_run(program: &[Op]) -> Option<isize> {
+    for (i, op) in program.iter().enumerate() {
+        let swapped = match op {
+            Op::Jmp(n) => Op::Nop(*n),
+            Op::Nop(n) => Op::Jmp(*n),
+            Op::Acc(_) => continue,
+        };
+
+        let mut mutated = program.to_vec();
+        mutated[i] = swapped;
+
+        if let RunResult::Finish(acc) = run(&mutated) {
+            return Some(acc);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Op> {
+        parse(
+            "nop +0
+acc +1
+jmp +4
+acc +3
+jmp -3
+acc -99
+acc +1
+jmp -4
+acc +6",
+        )
+    }
+
+    #[test]
+    fn detects_the_loop() {
+        assert_eq!(run(&sample()), RunResult::Loop(5));
+    }
+
+    #[test]
+    fn fixes_the_program_by_swapping_one_instruction() {
+        assert_eq!(fix_and_run(&sample()), Some(8));
+    }
+}